@@ -1,3 +1,7 @@
+use std::collections::VecDeque;
+
+use models::tree::Tree;
+
 pub struct Graph<T> {
     pub nodes: Vec<Node<T>>,
 }
@@ -21,7 +25,7 @@ pub enum Relationship {
 }
 
 impl Relationship {
-    fn invert(self: Self) -> Self {
+    fn invert(&self) -> Self {
         match self {
             Self::OneToOne => Self::OneToOne,
             Self::OneToMany => Self::ManyToOne,
@@ -29,6 +33,28 @@ impl Relationship {
             Self::ManyToMany => Self::ManyToMany,
         }
     }
+
+    // cardinality expresses a relationship as (from_many, to_many) so hops can be
+    // composed: OneToOne=(false,false), OneToMany=(false,true),
+    // ManyToOne=(true,false), ManyToMany=(true,true).
+    fn cardinality(&self) -> (bool, bool) {
+        match self {
+            Self::OneToOne => (false, false),
+            Self::OneToMany => (false, true),
+            Self::ManyToOne => (true, false),
+            Self::ManyToMany => (true, true),
+        }
+    }
+
+    // from_cardinality maps a (from_many, to_many) pair back to a Relationship.
+    fn from_cardinality(pair: (bool, bool)) -> Self {
+        match pair {
+            (false, false) => Self::OneToOne,
+            (false, true) => Self::OneToMany,
+            (true, false) => Self::ManyToOne,
+            (true, true) => Self::ManyToMany,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -46,13 +72,257 @@ impl<T> Graph<T> {
     }
 }
 
+impl<T: PartialEq> Graph<T> {
+    // index returns the dense index assigned to a node type, matching the order
+    // nodes are declared in the graph.
+    fn index(&self, typ: &T) -> Option<usize> {
+        self.nodes.iter().position(|n| &n.typ == typ)
+    }
+
+    // reachability builds the transitive reachability closure as a square bit
+    // matrix. Each node is assigned a dense index and a row of ceil(n/64) words
+    // whose bits mark the targets it can reach; reverse edges are included when
+    // include_reverse is set so a ManyToOne target becomes traversable backwards.
+    // The closure is computed Warshall-style: for each intermediate k, every row
+    // i that can already reach k absorbs row k.
+    fn reachability(&self, include_reverse: bool) -> Reachability {
+        let elements = self.nodes.len();
+        let words = elements.div_ceil(64);
+        let mut rows = vec![0u64; elements * words];
+        let mut set = |i: usize, j: usize| rows[i * words + j / 64] |= 1u64 << (j % 64);
+        for link in self.links() {
+            // from/to are guaranteed to resolve for any well formed graph.
+            if let (Some(from), Some(to)) = (self.index(link.from), self.index(link.to)) {
+                set(from, to);
+                if include_reverse {
+                    set(to, from);
+                }
+            }
+        }
+        for k in 0..elements {
+            for i in 0..elements {
+                if rows[i * words + k / 64] & (1u64 << (k % 64)) != 0 {
+                    for j in 0..words {
+                        rows[i * words + j] |= rows[k * words + j];
+                    }
+                }
+            }
+        }
+        Reachability { rows, words }
+    }
+
+    // reachable reports whether to can be reached from from by following
+    // relationships, optionally through inverted reverse edges.
+    pub fn reachable(&self, from: &T, to: &T, include_reverse: bool) -> bool {
+        match (self.index(from), self.index(to)) {
+            (Some(from), Some(to)) => self.reachability(include_reverse).contains(from, to),
+            _ => false,
+        }
+    }
+
+    // reachable_set returns every node type reachable from from, in declaration
+    // order, optionally through inverted reverse edges.
+    pub fn reachable_set(&self, from: &T, include_reverse: bool) -> Vec<&T> {
+        let Some(from) = self.index(from) else {
+            return vec![];
+        };
+        let matrix = self.reachability(include_reverse);
+        self.nodes.iter().enumerate()
+            .filter(|(to, _)| matrix.contains(from, *to))
+            .map(|(_, n)| &n.typ)
+            .collect()
+    }
+
+    // route runs a BFS over links() treating every link as usable in both
+    // directions, tracking the predecessor of each visited node to reconstruct
+    // the shortest edge sequence. Each returned step is the index into links()
+    // plus whether it was traversed in its declared (forward) direction.
+    fn route(&self, from: &T, to: &T) -> Option<Vec<(usize, bool)>> {
+        let from = self.index(from)?;
+        let to = self.index(to)?;
+        let links = self.links();
+        let edges: Vec<(usize, usize)> = links.iter()
+            .map(|l| (self.index(l.from).unwrap(), self.index(l.to).unwrap()))
+            .collect();
+        // pred[node] = (predecessor node, link index, traversed forward).
+        let mut pred: Vec<Option<(usize, usize, bool)>> = vec![None; self.nodes.len()];
+        let mut queue = VecDeque::new();
+        queue.push_back(from);
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                break;
+            }
+            for (i, &(ef, et)) in edges.iter().enumerate() {
+                let next = if ef == node {
+                    Some((et, true))
+                } else if et == node {
+                    Some((ef, false))
+                } else {
+                    None
+                };
+                if let Some((next, forward)) = next {
+                    if next != from && pred[next].is_none() {
+                        pred[next] = Some((node, i, forward));
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        if from == to {
+            return Some(vec![]);
+        }
+        let mut steps = Vec::new();
+        let mut current = to;
+        while let Some((prev, link, forward)) = pred[current] {
+            steps.push((link, forward));
+            current = prev;
+        }
+        if current != from {
+            return None;
+        }
+        steps.reverse();
+        Some(steps)
+    }
+
+    // path returns the shortest sequence of schema links connecting from to to,
+    // in traversal order from from toward to, or None when the two types are
+    // unconnected. Each hop is the Link exactly as declared in the graph, so the
+    // result is orientation-agnostic: a reverse query yields the same declared
+    // links a forward one would, just in the opposite order. Use path_cardinality
+    // when the composed, direction-aware cardinality of the walk is needed.
+    pub fn path(&self, from: &T, to: &T) -> Option<Vec<Link<'_, T>>> {
+        let links = self.links();
+        let steps = self.route(from, to)?;
+        Some(steps.into_iter().map(|(i, _)| Link {
+            from: links[i].from,
+            to: links[i].to,
+            rel: links[i].rel,
+        }).collect())
+    }
+
+    // path_cardinality composes the cardinality of every hop along the shortest
+    // path, inverting hops traversed against their declared direction, so the
+    // planner can detect when a join fans out to "many".
+    pub fn path_cardinality(&self, from: &T, to: &T) -> Option<Relationship> {
+        let links = self.links();
+        let steps = self.route(from, to)?;
+        let pair = steps.into_iter().fold((false, false), |(fm, tm), (i, forward)| {
+            // Reverse hops contribute the inverted relationship's cardinality.
+            let (a, b) = if forward {
+                links[i].rel.cardinality()
+            } else {
+                links[i].rel.invert().cardinality()
+            };
+            (fm || a, tm || b)
+        });
+        Some(Relationship::from_cardinality(pair))
+    }
+}
+
+// ValidationError reports a query edge whose child has no matching Link in the
+// schema, carrying the offending child's value and the child's path_to_root so
+// callers can point at the exact query location.
+#[derive(Debug, PartialEq)]
+pub struct ValidationError<'a, T> {
+    pub value: &'a T,
+    pub path: Vec<&'a Tree<T>>,
+}
+
+// ValidatedHop records a query edge that matched a schema Link together with the
+// discovered relationship, so downstream planning knows the cardinality of the hop.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ValidatedHop<'a, T> {
+    pub from: &'a T,
+    pub to: &'a T,
+    pub rel: Relationship,
+}
+
+impl<T: PartialEq> Graph<T> {
+    // validate checks that every parent->child step in the query tree corresponds
+    // to a Link in the schema, honoring invert for edges traversed in reverse. It
+    // returns the discovered hops in document order on success, or every offending
+    // edge with its query path on failure.
+    pub fn validate<'a>(
+        &self,
+        query: &'a Tree<T>,
+    ) -> Result<Vec<ValidatedHop<'a, T>>, Vec<ValidationError<'a, T>>> {
+        let mut hops = Vec::new();
+        let mut errors = Vec::new();
+        let mut ancestors = Vec::new();
+        self.validate_node(query, &mut ancestors, &mut hops, &mut errors);
+        if errors.is_empty() {
+            Ok(hops)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn validate_node<'a>(
+        &self,
+        node: &'a Tree<T>,
+        ancestors: &mut Vec<&'a Tree<T>>,
+        hops: &mut Vec<ValidatedHop<'a, T>>,
+        errors: &mut Vec<ValidationError<'a, T>>,
+    ) {
+        for child in node.children.iter() {
+            match self.resolve(&node.value, &child.value) {
+                Some(rel) => hops.push(ValidatedHop {
+                    from: &node.value,
+                    to: &child.value,
+                    rel,
+                }),
+                None => {
+                    // Mirror TreeIterationState::path_to_root: parent, then up to the root.
+                    let mut path = vec![node];
+                    path.extend(ancestors.iter().rev().copied());
+                    errors.push(ValidationError {
+                        value: &child.value,
+                        path,
+                    });
+                }
+            }
+            ancestors.push(node);
+            self.validate_node(child, ancestors, hops, errors);
+            ancestors.pop();
+        }
+    }
+
+    // resolve finds the relationship describing a parent->child edge, matching a
+    // Link in either direction and inverting the cardinality for reverse links.
+    fn resolve(&self, from: &T, to: &T) -> Option<Relationship> {
+        for link in self.links() {
+            if link.from == from && link.to == to {
+                return Some(Relationship::from_cardinality(link.rel.cardinality()));
+            }
+            if link.from == to && link.to == from {
+                let (a, b) = link.rel.cardinality();
+                return Some(Relationship::from_cardinality((b, a)));
+            }
+        }
+        None
+    }
+}
+
+// Reachability holds the packed transitive closure bit matrix, rows of words
+// bitsets indexed by the dense node index.
+struct Reachability {
+    rows: Vec<u64>,
+    words: usize,
+}
+
+impl Reachability {
+    fn contains(&self, from: usize, to: usize) -> bool {
+        self.rows[from * self.words + to / 64] & (1u64 << (to % 64)) != 0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[derive(Debug, PartialEq, Eq)]
     enum TestNodes {
-        A, B,
+        A, B, C,
     }
 
     fn vec_compare<T: std::cmp::PartialEq>(va: &Vec<T>, vb:  &Vec<T>) -> bool {
@@ -71,6 +341,116 @@ mod tests {
             Link{from: &TestNodes::A, to: &TestNodes::B, rel: &Relationship::OneToOne }
         ]));
     }
+
+    fn chain_graph() -> Graph<TestNodes> {
+        Graph{nodes:vec![
+            Node{typ: TestNodes::A, targets:vec![
+                Target{typ: TestNodes::B, rel:Relationship::OneToMany}
+            ]},
+            Node{typ: TestNodes::B, targets:vec![
+                Target{typ: TestNodes::C, rel:Relationship::ManyToOne}
+            ]},
+            Node{typ: TestNodes::C, targets:vec![]},
+        ]}
+    }
+
+    #[test]
+    fn test_reachable_transitive() {
+        let graph = chain_graph();
+        assert!(graph.reachable(&TestNodes::A, &TestNodes::C, false));
+        assert!(!graph.reachable(&TestNodes::C, &TestNodes::A, false));
+    }
+
+    #[test]
+    fn test_reachable_reverse() {
+        let graph = chain_graph();
+        assert!(!graph.reachable(&TestNodes::C, &TestNodes::A, false));
+        assert!(graph.reachable(&TestNodes::C, &TestNodes::A, true));
+    }
+
+    #[test]
+    fn test_reachable_set() {
+        let graph = chain_graph();
+        assert!(vec_compare(&graph.reachable_set(&TestNodes::A, false),
+            &vec![&TestNodes::B, &TestNodes::C]));
+    }
+
+    #[test]
+    fn test_path_forward() {
+        let graph = chain_graph();
+        assert!(vec_compare(&graph.path(&TestNodes::A, &TestNodes::C).unwrap(), &vec![
+            Link{from: &TestNodes::A, to: &TestNodes::B, rel: &Relationship::OneToMany},
+            Link{from: &TestNodes::B, to: &TestNodes::C, rel: &Relationship::ManyToOne},
+        ]));
+    }
+
+    #[test]
+    fn test_path_reverse() {
+        let graph = chain_graph();
+        // Reverse traversal yields the same declared links in traversal order.
+        assert!(vec_compare(&graph.path(&TestNodes::C, &TestNodes::A).unwrap(), &vec![
+            Link{from: &TestNodes::B, to: &TestNodes::C, rel: &Relationship::ManyToOne},
+            Link{from: &TestNodes::A, to: &TestNodes::B, rel: &Relationship::OneToMany},
+        ]));
+    }
+
+    #[test]
+    fn test_path_missing() {
+        let graph = Graph{nodes:vec![
+            Node{typ: TestNodes::A, targets:vec![]},
+            Node{typ: TestNodes::B, targets:vec![]},
+        ]};
+        assert!(graph.path(&TestNodes::A, &TestNodes::B).is_none());
+    }
+
+    #[test]
+    fn test_path_cardinality_explodes() {
+        let graph = chain_graph();
+        assert_eq!(graph.path_cardinality(&TestNodes::A, &TestNodes::C), Some(Relationship::ManyToMany));
+    }
+
+    #[test]
+    fn test_path_cardinality_reverse() {
+        let graph = chain_graph();
+        assert_eq!(graph.path_cardinality(&TestNodes::C, &TestNodes::A), Some(Relationship::ManyToMany));
+    }
+
+    #[test]
+    fn test_validate_forward() {
+        let graph = chain_graph();
+        let query = Tree{value: TestNodes::A, children: vec![
+            Tree{value: TestNodes::B, children: vec![
+                Tree{value: TestNodes::C, children: vec![]},
+            ]},
+        ]};
+        assert_eq!(graph.validate(&query), Ok(vec![
+            ValidatedHop{from: &TestNodes::A, to: &TestNodes::B, rel: Relationship::OneToMany},
+            ValidatedHop{from: &TestNodes::B, to: &TestNodes::C, rel: Relationship::ManyToOne},
+        ]));
+    }
+
+    #[test]
+    fn test_validate_reverse_edge_inverts() {
+        let graph = chain_graph();
+        let query = Tree{value: TestNodes::C, children: vec![
+            Tree{value: TestNodes::B, children: vec![]},
+        ]};
+        assert_eq!(graph.validate(&query), Ok(vec![
+            ValidatedHop{from: &TestNodes::C, to: &TestNodes::B, rel: Relationship::OneToMany},
+        ]));
+    }
+
+    #[test]
+    fn test_validate_reports_path_to_root() {
+        let graph = chain_graph();
+        let query = Tree{value: TestNodes::A, children: vec![
+            Tree{value: TestNodes::C, children: vec![]},
+        ]};
+        let errors = graph.validate(&query).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].value, &TestNodes::C);
+        assert!(vec_compare(&errors[0].path.iter().map(|n| &n.value).collect(), &vec![&TestNodes::A]));
+    }
 }
 
 
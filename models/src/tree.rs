@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 
 // Tree represents a standard tree data structure
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Tree<T> {
     pub value: T,
     pub children: Vec<Tree<T>>,
@@ -18,6 +18,37 @@ impl<'a, T: Clone + 'a> Tree<T> {
     ) -> TreeIterator<'a, 'b, T> {
         TreeIterator::new_with_condition(self, condition)
     }
+
+    // dfs walks the tree in preorder (document order), visiting each node before
+    // its children and each child left to right. It yields the same
+    // TreeIterationState as iter() so path_to_root() keeps working.
+    fn dfs(&'a self) -> TreeDfsIterator<'a, T> {
+        TreeDfsIterator::new(self)
+    }
+
+    // scan threads an accumulated state down the tree: the root starts from init
+    // and every other node derives its state from its parent's via f. It walks
+    // breadth first to match iter(), yielding each node paired with its computed
+    // state so short-circuiting conditions can read the state before descending.
+    fn scan<'b, S>(
+        &'a self,
+        init: S,
+        f: impl Fn(&S, &T) -> S + 'b,
+    ) -> TreeScanIterator<'a, 'b, T, S> {
+        TreeScanIterator::new(self, init, f)
+    }
+}
+
+impl<T> Tree<T> {
+    // add_child appends a new leaf child holding value and returns a mutable
+    // reference to it so callers can keep descending while building a tree.
+    fn add_child(&mut self, value: T) -> &mut Tree<T> {
+        self.children.push(Tree {
+            value,
+            children: vec![],
+        });
+        self.children.last_mut().unwrap()
+    }
 }
 
 impl<'a, T: 'a + std::cmp::PartialEq> Tree<T> {
@@ -38,6 +69,26 @@ impl<'a, T: 'a + std::cmp::PartialEq> Tree<T> {
         }
         false
     }
+
+    // at descends child-by-child matching each value in path in order, returning
+    // the addressed node or None on the first value that has no matching child.
+    fn at(&self, path: &[T]) -> Option<&Tree<T>> {
+        let mut current = self;
+        for value in path {
+            current = current.children.iter().find(|c| &c.value == value)?;
+        }
+        Some(current)
+    }
+
+    // at_mut is the mutable counterpart to at, handing back a mutable reference
+    // to the addressed node so callers can extend the tree at a known path.
+    fn at_mut(&mut self, path: &[T]) -> Option<&mut Tree<T>> {
+        let mut current = self;
+        for value in path {
+            current = current.children.iter_mut().find(|c| &c.value == value)?;
+        }
+        Some(current)
+    }
 }
 
 // TreeIterator<T> is an iterator for a Tree<T> that provides optional conditional.
@@ -46,6 +97,72 @@ pub struct TreeIterator<'a: 'b, 'b, T: Clone> {
     condition: Box<dyn Fn(&'b T) -> bool + 'b>,
 }
 
+// TreeDfsIterator<T> is a preorder iterator for a Tree<T> backed by an explicit
+// stack so document order is preserved without recursion.
+pub struct TreeDfsIterator<'a, T: Clone> {
+    stack: Vec<TreeIterationState<'a, T>>,
+}
+
+impl<'a, T: Clone> TreeDfsIterator<'a, T> {
+    // new creates a new TreeDfsIterator<T> for a given Tree<T> that iterates all
+    // nodes in a preorder depth first approach.
+    fn new(tree: &'a Tree<T>) -> TreeDfsIterator<'a, T> {
+        TreeDfsIterator {
+            stack: vec![TreeIterationState { tree, parent: None }],
+        }
+    }
+}
+
+impl<'a, T: Clone> Iterator for TreeDfsIterator<'a, T> {
+    type Item = TreeIterationState<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        // Push children in reverse so the leftmost child is popped (visited) first.
+        for child in node.tree.children.iter().rev() {
+            self.stack.push(TreeIterationState {
+                tree: child,
+                parent: Some(Box::new(node.clone())),
+            });
+        }
+        Some(node)
+    }
+}
+
+// TreeScanIterator<T, S> walks a Tree<T> breadth first while threading an
+// accumulated state S down from each parent to its children.
+pub struct TreeScanIterator<'a: 'b, 'b, T: Clone, S> {
+    queue: VecDeque<(&'a Tree<T>, S)>,
+    f: Box<dyn Fn(&S, &T) -> S + 'b>,
+}
+
+impl<'a: 'b, 'b, T: Clone, S> TreeScanIterator<'a, 'b, T, S> {
+    fn new(
+        tree: &'a Tree<T>,
+        init: S,
+        f: impl Fn(&S, &T) -> S + 'b,
+    ) -> TreeScanIterator<'a, 'b, T, S> {
+        let mut queue = VecDeque::new();
+        queue.push_back((tree, init));
+        TreeScanIterator {
+            queue,
+            f: Box::new(f),
+        }
+    }
+}
+
+impl<'a: 'b, 'b, T: Clone, S> Iterator for TreeScanIterator<'a, 'b, T, S> {
+    type Item = (&'a Tree<T>, S);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, state) = self.queue.pop_front()?;
+        // Derive each child's state once, before this node's state is yielded.
+        for child in node.children.iter() {
+            let child_state = (self.f)(&state, &child.value);
+            self.queue.push_back((child, child_state));
+        }
+        Some((node, state))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TreeIterationState<'a, T: Clone> {
     tree: &'a Tree<T>,
@@ -148,6 +265,64 @@ mod tests {
         ]}, vec![TestValues::A, TestValues::B, TestValues::B, TestValues::C, TestValues::C]),
     }
 
+    macro_rules! dfs_iteration_test {
+        ($($name:ident: $value:expr,)*) => {$(
+            #[test]
+            fn $name() {
+                let (tree, expected) = $value;
+                assert!(vec_compare(&expected, &tree.dfs().map(|n| n.tree.value).collect()));
+            }
+        )*}
+    }
+
+    dfs_iteration_test! {
+        dfs_single_iteration: (Tree{value: TestValues::A, children: vec![]}, vec![TestValues::A]),
+        dfs_depth_check_iteration: (Tree{value: TestValues::A, children: vec![
+            Tree{value: TestValues::B, children: vec![
+                Tree{value: TestValues::C, children:vec![]},
+            ]},
+            Tree{value: TestValues::D, children: vec![
+                Tree{value: TestValues::C, children:vec![]},
+            ]},
+        ]}, vec![TestValues::A, TestValues::B, TestValues::C, TestValues::D, TestValues::C]),
+    }
+
+    #[test]
+    fn at_descends_to_addressed_node() {
+        let tree = Tree{value: TestValues::A, children: vec![
+            Tree{value: TestValues::B, children: vec![
+                Tree{value: TestValues::C, children:vec![]},
+            ]},
+        ]};
+        assert_eq!(tree.at(&[]).map(|n| n.value), Some(TestValues::A));
+        assert_eq!(tree.at(&[TestValues::B, TestValues::C]).map(|n| n.value), Some(TestValues::C));
+        assert!(tree.at(&[TestValues::B, TestValues::D]).is_none());
+    }
+
+    #[test]
+    fn add_child_and_at_mut_build_tree() {
+        let mut tree = Tree{value: TestValues::A, children: vec![]};
+        tree.add_child(TestValues::B).add_child(TestValues::C);
+        assert_eq!(tree.at(&[TestValues::B, TestValues::C]).map(|n| n.value), Some(TestValues::C));
+        tree.at_mut(&[TestValues::B]).unwrap().add_child(TestValues::D);
+        assert_eq!(tree.at(&[TestValues::B, TestValues::D]).map(|n| n.value), Some(TestValues::D));
+    }
+
+    #[test]
+    fn scan_threads_depth_down_tree() {
+        let tree = Tree{value: TestValues::A, children: vec![
+            Tree{value: TestValues::B, children: vec![
+                Tree{value: TestValues::C, children:vec![]},
+            ]},
+            Tree{value: TestValues::B, children: vec![]},
+        ]};
+        let depths: Vec<(TestValues, u32)> = tree.scan(0u32, |d, _| d + 1)
+            .map(|(n, d)| (n.value, d)).collect();
+        assert!(vec_compare(&depths, &vec![
+            (TestValues::A, 0), (TestValues::B, 1), (TestValues::B, 1), (TestValues::C, 2),
+        ]));
+    }
+
     macro_rules! iteration_condition_test {
         ($($name:ident: $value:expr => $condition:expr,)*) => {$(
             #[test]